@@ -0,0 +1,25 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+pub mod ledger;
+pub use ledger::*;
+
+pub mod rocksdb_backend;
+pub mod sled_backend;
+
+// Re-exported so the column constants (`COL_META`, `COL_COMMITMENT`, `NUM_COLS`, ...)
+// and other schema items `ledger.rs` relies on via `use crate::*` resolve from here.
+pub use snarkvm_dpc::*;
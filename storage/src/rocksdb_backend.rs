@@ -0,0 +1,133 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use snarkvm_dpc::{errors::StorageError, DatabaseTransaction, Op, Storage};
+
+use std::{path::Path, sync::RwLock};
+
+/// The original `Storage` backend, wrapping a RocksDB instance that may be
+/// opened as primary (read-write) or as a read-only secondary trailing a
+/// primary elsewhere. This is the backend `SecondaryCapable` was written for:
+/// RocksDB's secondary-instance mode is what lets `catch_up_secondary` and
+/// `Ledger::spawn_secondary_syncer` actually observe the primary's writes.
+pub struct RocksDbStorage {
+    db: RwLock<rocksdb::DB>,
+    is_secondary: bool,
+}
+
+impl Storage for RocksDbStorage {
+    fn open(
+        primary_path: Option<&Path>,
+        secondary_path: Option<&Path>,
+        recovery_mode: RecoveryMode,
+    ) -> Result<Self, StorageError> {
+        let mut options = rocksdb::Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        options.set_wal_recovery_mode(to_rocksdb_recovery_mode(recovery_mode));
+
+        let primary_path =
+            primary_path.ok_or_else(|| StorageError::Message("RocksDbStorage requires a primary path".into()))?;
+
+        let (db, is_secondary) = match secondary_path {
+            Some(secondary_path) => {
+                let db = rocksdb::DB::open_cf_as_secondary(&options, primary_path, secondary_path, column_families())
+                    .map_err(|e| StorageError::Message(e.to_string()))?;
+                (db, true)
+            }
+            None => {
+                let db = rocksdb::DB::open_cf(&options, primary_path, column_families())
+                    .map_err(|e| StorageError::Message(e.to_string()))?;
+                (db, false)
+            }
+        };
+
+        Ok(Self { db: RwLock::new(db), is_secondary })
+    }
+
+    fn get(&self, col: u32, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        let db = self.db.read().expect("RocksDbStorage lock poisoned");
+        let cf = column_family(&db, col)?;
+
+        db.get_cf(cf, key).map_err(|e| StorageError::Message(e.to_string()))
+    }
+
+    fn get_col(&self, col: u32) -> Result<Vec<(Vec<u8>, Vec<u8>)>, StorageError> {
+        let db = self.db.read().expect("RocksDbStorage lock poisoned");
+        let cf = column_family(&db, col)?;
+
+        Ok(db
+            .iterator_cf(cf, rocksdb::IteratorMode::Start)
+            .map(|(key, value)| (key.to_vec(), value.to_vec()))
+            .collect())
+    }
+
+    fn batch(&self, transaction: DatabaseTransaction) -> Result<(), StorageError> {
+        let db = self.db.read().expect("RocksDbStorage lock poisoned");
+        let mut batch = rocksdb::WriteBatch::default();
+
+        for op in transaction.0 {
+            match op {
+                Op::Insert { col, key, value } => batch.put_cf(column_family(&db, col)?, key, value),
+                Op::Delete { col, key } => batch.delete_cf(column_family(&db, col)?, key),
+            }
+        }
+
+        db.write(batch).map_err(|e| StorageError::Message(e.to_string()))
+    }
+}
+
+impl SecondaryCapable for RocksDbStorage {
+    fn try_catch_up_with_primary(&self) -> Result<(), StorageError> {
+        if !self.is_secondary {
+            return Err(StorageError::Message("not a secondary RocksDbStorage instance".into()));
+        }
+
+        self.db
+            .read()
+            .expect("RocksDbStorage lock poisoned")
+            .try_catch_up_with_primary()
+            .map_err(|e| StorageError::Message(e.to_string()))
+    }
+}
+
+fn to_rocksdb_recovery_mode(recovery_mode: RecoveryMode) -> rocksdb::DBRecoveryMode {
+    match recovery_mode {
+        RecoveryMode::TolerateCorruptedTailRecords => rocksdb::DBRecoveryMode::TolerateCorruptedTailRecords,
+        RecoveryMode::PointInTime => rocksdb::DBRecoveryMode::PointInTime,
+        RecoveryMode::AbsoluteConsistency => rocksdb::DBRecoveryMode::AbsoluteConsistency,
+        RecoveryMode::SkipAnyCorruptedRecord => rocksdb::DBRecoveryMode::SkipAnyCorruptedRecord,
+    }
+}
+
+/// Column families this backend registers with RocksDB up front: every column
+/// the ledger's schema defines (`0..NUM_COLS`), plus `COL_CHT`, which sits one
+/// past that range. `open_cf`/`open_cf_as_secondary` require every existing
+/// column family to be listed, and `cf_handle` returns `None` for any column
+/// left out - so this must cover the whole set the ledger actually reads and
+/// writes, not just the handful `RocksDbStorage` itself names directly.
+fn column_families() -> Vec<rocksdb::ColumnFamilyDescriptor> {
+    (0..NUM_COLS)
+        .chain(std::iter::once(COL_CHT))
+        .map(|col| rocksdb::ColumnFamilyDescriptor::new(col.to_string(), rocksdb::Options::default()))
+        .collect()
+}
+
+fn column_family(db: &rocksdb::DB, col: u32) -> Result<&rocksdb::ColumnFamily, StorageError> {
+    db.cf_handle(&col.to_string())
+        .ok_or_else(|| StorageError::Message(format!("no column family for column {}", col)))
+}
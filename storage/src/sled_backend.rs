@@ -0,0 +1,101 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use snarkvm_dpc::{errors::StorageError, DatabaseTransaction, Op, Storage};
+
+use std::{collections::HashMap, path::Path};
+
+/// An embedded, pure-Rust `Storage` backend built on `sled`, for operators who
+/// don't want to run RocksDB. Unlike `RocksDbStorage`, `sled` has no notion of a
+/// second process trailing a primary's writes, so this backend does not
+/// implement `SecondaryCapable`: `open` rejects a secondary path outright rather
+/// than producing an instance that looks open but never catches up.
+pub struct SledStorage {
+    db: sled::Db,
+}
+
+impl Storage for SledStorage {
+    fn open(
+        primary_path: Option<&Path>,
+        secondary_path: Option<&Path>,
+        recovery_mode: RecoveryMode,
+    ) -> Result<Self, StorageError> {
+        if secondary_path.is_some() {
+            return Err(StorageError::Message(
+                "the sled storage backend does not support a live secondary read replica".into(),
+            ));
+        }
+
+        // `sled` has no separate WAL recovery knobs to select between; every open
+        // already recovers to the last consistent point, which lines up with
+        // `RecoveryMode::PointInTime`. Anything stricter is not representable.
+        if recovery_mode == RecoveryMode::AbsoluteConsistency {
+            return Err(StorageError::Message(
+                "the sled storage backend cannot guarantee AbsoluteConsistency recovery".into(),
+            ));
+        }
+
+        let db = match primary_path {
+            Some(path) => sled::open(path).map_err(|e| StorageError::Message(e.to_string()))?,
+            None => sled::Config::new().temporary(true).open().map_err(|e| StorageError::Message(e.to_string()))?,
+        };
+
+        Ok(Self { db })
+    }
+
+    fn get(&self, col: u32, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        let tree = self.db.open_tree(col.to_le_bytes()).map_err(|e| StorageError::Message(e.to_string()))?;
+
+        Ok(tree.get(key).map_err(|e| StorageError::Message(e.to_string()))?.map(|value| value.to_vec()))
+    }
+
+    fn get_col(&self, col: u32) -> Result<Vec<(Vec<u8>, Vec<u8>)>, StorageError> {
+        let tree = self.db.open_tree(col.to_le_bytes()).map_err(|e| StorageError::Message(e.to_string()))?;
+
+        tree.iter()
+            .map(|entry| {
+                let (key, value) = entry.map_err(|e| StorageError::Message(e.to_string()))?;
+                Ok((key.to_vec(), value.to_vec()))
+            })
+            .collect()
+    }
+
+    fn batch(&self, transaction: DatabaseTransaction) -> Result<(), StorageError> {
+        // Group ops per column into a `sled::Batch` so each tree applies its share
+        // of the transaction atomically, rather than inserting/removing one key at
+        // a time. The per-column batches are applied one after another, so a
+        // transaction spanning several columns is not atomic as a whole: a crash
+        // or error between two columns leaves it partially applied.
+        let mut batches: HashMap<u32, sled::Batch> = HashMap::new();
+
+        for op in transaction.0 {
+            match op {
+                Op::Insert { col, key, value } => batches.entry(col).or_default().insert(key, value),
+                Op::Delete { col, key } => batches.entry(col).or_default().remove(key),
+            }
+        }
+
+        for (col, batch) in batches {
+            let tree = self.db.open_tree(col.to_le_bytes()).map_err(|e| StorageError::Message(e.to_string()))?;
+            tree.apply_batch(batch).map_err(|e| StorageError::Message(e.to_string()))?;
+        }
+
+        self.db.flush().map_err(|e| StorageError::Message(e.to_string()))?;
+
+        Ok(())
+    }
+}